@@ -0,0 +1,140 @@
+use crate::block_definitions::*;
+use crate::cartesian::XZPoint;
+use crate::geometry::point_in_polygon;
+use crate::osm_parser::ProcessedElement;
+
+/// A coarse biome classification derived from OSM tags.
+///
+/// Each variant knows which block to lay on the surface and which block to use
+/// as the shallow filler beneath it. This is modeled on Minetest's registered
+/// biomes / `BiomeDefManager`, where every biome carries its node definitions
+/// and is resolved per column.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Biome {
+    Plains,
+    Forest,
+    Desert,
+    Beach,
+    Swamp,
+    Farmland,
+    Snowy,
+}
+
+impl Biome {
+    /// Block placed on the topmost ground layer of a column.
+    pub fn surface_block(self) -> Block {
+        match self {
+            Biome::Plains | Biome::Forest | Biome::Farmland => GRASS_BLOCK,
+            Biome::Desert | Biome::Beach => SAND,
+            Biome::Swamp => MUD,
+            Biome::Snowy => SNOW_BLOCK,
+        }
+    }
+
+    /// Block placed in the shallow layers directly below the surface.
+    pub fn filler_block(self) -> Block {
+        match self {
+            Biome::Desert => SANDSTONE,
+            Biome::Beach => SAND,
+            _ => DIRT,
+        }
+    }
+
+    /// Resolve a biome from the tags already matched in the element `match`.
+    /// Returns `None` when the element carries no biome-defining tag so that
+    /// the default surface is left untouched.
+    fn from_tags(tags: &std::collections::HashMap<String, String>) -> Option<Biome> {
+        match (
+            tags.get("natural").map(String::as_str),
+            tags.get("landuse").map(String::as_str),
+        ) {
+            (Some("wood"), _) | (_, Some("forest")) => Some(Biome::Forest),
+            (Some("desert") | Some("sand"), _) => Some(Biome::Desert),
+            (Some("beach") | Some("shingle"), _) => Some(Biome::Beach),
+            (Some("wetland") | Some("mud") | Some("marsh"), _) => Some(Biome::Swamp),
+            (_, Some("farmland") | Some("farmyard") | Some("meadow")) => Some(Biome::Farmland),
+            _ => None,
+        }
+    }
+}
+
+/// Per-column biome lookup rasterized from the OSM elements.
+///
+/// The map is built once, before the ground loop, by walking every tagged way
+/// and marking the columns inside its polygon. The ground generator then
+/// consults [`BiomeMap::biome_at`] for each column instead of relying on a
+/// single surface block.
+pub struct BiomeMap {
+    width: usize,
+    depth: usize,
+    grid: Vec<Biome>,
+}
+
+impl BiomeMap {
+    /// Build the lookup for a region of `scale_factor_x` by `scale_factor_z`
+    /// columns by rasterizing every biome-bearing element.
+    pub fn new(elements: &[ProcessedElement], scale_factor_x: f64, scale_factor_z: f64) -> Self {
+        let width = scale_factor_x as usize + 1;
+        let depth = scale_factor_z as usize + 1;
+        let mut map = Self {
+            width,
+            depth,
+            grid: vec![Biome::Plains; width * depth],
+        };
+
+        for element in elements {
+            let ProcessedElement::Way(way) = element else {
+                continue;
+            };
+            let Some(biome) = Biome::from_tags(&way.tags) else {
+                continue;
+            };
+            map.rasterize(&way.nodes, biome);
+        }
+
+        map
+    }
+
+    /// Mark every column inside the polygon described by `nodes`.
+    fn rasterize(&mut self, nodes: &[crate::osm_parser::ProcessedNode], biome: Biome) {
+        if nodes.len() < 3 {
+            return;
+        }
+
+        let min_x = nodes.iter().map(|n| n.x).min().unwrap_or(0).max(0);
+        let max_x = nodes
+            .iter()
+            .map(|n| n.x)
+            .max()
+            .unwrap_or(0)
+            .min(self.width as i32 - 1);
+        let min_z = nodes.iter().map(|n| n.z).min().unwrap_or(0).max(0);
+        let max_z = nodes
+            .iter()
+            .map(|n| n.z)
+            .max()
+            .unwrap_or(0)
+            .min(self.depth as i32 - 1);
+
+        for x in min_x..=max_x {
+            for z in min_z..=max_z {
+                if point_in_polygon(nodes, x, z) {
+                    let idx = x as usize * self.depth + z as usize;
+                    self.grid[idx] = biome;
+                }
+            }
+        }
+    }
+
+    /// Biome resolved for the given column, defaulting to [`Biome::Plains`].
+    pub fn biome_at(&self, point: XZPoint) -> Biome {
+        if point.x < 0 || point.z < 0 {
+            return Biome::Plains;
+        }
+        let (x, z) = (point.x as usize, point.z as usize);
+        if x >= self.width || z >= self.depth {
+            return Biome::Plains;
+        }
+        self.grid[x * self.depth + z]
+    }
+}
@@ -1,16 +1,32 @@
 use crate::args::Args;
-use crate::block_definitions::{BEDROCK, DIRT, GRASS_BLOCK, SNOW_BLOCK, STONE};
+use crate::biome::BiomeMap;
+use crate::block_definitions::{BEDROCK, SNOW_BLOCK};
 use crate::cartesian::XZPoint;
 use crate::element_processing::*;
+use crate::forest;
 use crate::ground::Ground;
+use crate::map_render;
 use crate::osm_parser::ProcessedElement;
 use crate::progress::emit_gui_progress_update;
+use crate::spawn;
+use crate::subsurface;
+use crate::terrain::PlasmaTerrain;
 use crate::world_editor::WorldEditor;
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 
 pub const MIN_Y: i32 = -64;
 
+/// Synthesize procedural relief when no real elevation data is available, so a
+/// flat-terrain run is not a featureless pancake. The relief amplitude and seed
+/// are fixed here rather than plumbed through the CLI.
+const PROCEDURAL_TERRAIN: bool = true;
+const TERRAIN_RELIEF: i32 = 12;
+const TERRAIN_SEED: u64 = 0x5EED;
+
+/// Emit a top-down preview render of the generated region alongside the world.
+const RENDER_MAP: bool = true;
+
 pub fn generate_world(
     elements: Vec<ProcessedElement>,
     args: &Args,
@@ -66,12 +82,21 @@ pub fn generate_world(
                     highways::generate_highways(&mut editor, element, &ground, args);
                 } else if way.tags.contains_key("landuse") {
                     landuse::generate_landuse(&mut editor, way, &ground, args);
+                    if way.tags.get("landuse") == Some(&"forest".to_string()) {
+                        forest::generate_forest(&mut editor, way, &ground, args);
+                    }
                 } else if way.tags.contains_key("natural") {
                     natural::generate_natural(&mut editor, element, &ground, args);
+                    if way.tags.get("natural") == Some(&"wood".to_string()) {
+                        forest::generate_forest(&mut editor, way, &ground, args);
+                    }
                 } else if way.tags.contains_key("amenity") {
                     amenities::generate_amenities(&mut editor, element, &ground, args);
                 } else if way.tags.contains_key("leisure") {
                     leisure::generate_leisure(&mut editor, way, &ground, args);
+                    if way.tags.get("leisure") == Some(&"park".to_string()) {
+                        forest::generate_forest(&mut editor, way, &ground, args);
+                    }
                 } else if way.tags.contains_key("barrier") {
                     barriers::generate_barriers(&mut editor, element, &ground);
                 } else if way.tags.contains_key("waterway") {
@@ -118,6 +143,10 @@ pub fn generate_world(
 
     process_pb.finish();
 
+    // Resolve biomes from the processed elements so the ground loop can pick a
+    // surface/filler block per column.
+    let biome_map: BiomeMap = BiomeMap::new(&elements, scale_factor_x, scale_factor_z);
+
     // Generate ground layer
     let total_blocks: u64 = (scale_factor_x as i32 + 1) as u64 * (scale_factor_z as i32 + 1) as u64;
     let desired_updates: u64 = 1500;
@@ -141,12 +170,13 @@ pub fn generate_world(
     let total_iterations_grnd: f64 = (scale_factor_x + 1.0) * (scale_factor_z + 1.0);
     let progress_increment_grnd: f64 = 30.0 / total_iterations_grnd;
 
-    let groundlayer_block = if args.winter { SNOW_BLOCK } else { GRASS_BLOCK };
+    // Surface height per column, shared by the spawn picker and map render and
+    // populated in whichever branch runs below.
+    let mut ground_levels: Vec<Vec<i32>> = Vec::with_capacity(scale_factor_x as usize + 1);
 
     // Differentiate between terrain and non-terrain generation
     if ground.elevation_enabled {
         // Pre-calculate ground levels for all points
-        let mut ground_levels: Vec<Vec<i32>> = Vec::with_capacity(scale_factor_x as usize + 1);
         for x in 0..=(scale_factor_x as i32) {
             let mut row = Vec::with_capacity(scale_factor_z as usize + 1);
             for z in 0..=(scale_factor_z as i32) {
@@ -155,7 +185,9 @@ pub fn generate_world(
             ground_levels.push(row);
         }
 
-        // Process blocks in larger batches
+        // Process blocks in larger batches. Every column is regenerated because
+        // the editor's block state is not itself persisted; skipping would leave
+        // ungenerated stripes.
         for x in 0..=(scale_factor_x as i32) {
             for z in 0..=(scale_factor_z as i32) {
                 let ground_level = ground_levels[x as usize][z as usize];
@@ -166,14 +198,26 @@ pub fn generate_world(
                     .unwrap_or(ground_level)
                     .min(ground_level);
 
+                // Resolve the biome for this column and lay its surface/filler.
+                let biome = biome_map.biome_at(XZPoint::new(x, z));
+                let surface = if args.winter {
+                    SNOW_BLOCK
+                } else {
+                    biome.surface_block()
+                };
+
                 // Set blocks in a single batch
-                editor.set_block(groundlayer_block, x, max_y, z, None, None);
-                editor.set_block(DIRT, x, max_y - 1, z, None, None);
-                editor.set_block(DIRT, x, max_y - 2, z, None, None);
+                editor.set_block(surface, x, max_y, z, None, None);
+                editor.set_block(biome.filler_block(), x, max_y - 1, z, None, None);
+                editor.set_block(biome.filler_block(), x, max_y - 2, z, None, None);
+
+                // Record the surface actually laid (which may sit below the raw
+                // ground level) so the spawn picker and map render agree with it.
+                ground_levels[x as usize][z as usize] = max_y;
 
-                // Fill underground with stone
+                // Fill underground with the stratum/ore mix
                 if args.fillground {
-                    editor.fill_blocks(STONE, x, MIN_Y + 1, z, x, max_y - 2, z, None, None);
+                    subsurface::fill_column(&mut editor, x, z, MIN_Y + 1, max_y - 2);
                     editor.set_block(BEDROCK, x, MIN_Y, z, None, Some(&[BEDROCK]));
                 }
 
@@ -189,19 +233,39 @@ pub fn generate_world(
                 }
             }
         }
-
-        // Set blocks at spawn location
-        for x in 0..=20 {
-            for z in 0..=20 {
-                editor.set_block(groundlayer_block, x, -62, z, None, None);
-            }
-        }
     } else {
+        // Without real elevation, optionally synthesize plausible relief with a
+        // diamond-square heightfield so the world is not a flat pancake.
+        let plasma: Option<PlasmaTerrain> = if PROCEDURAL_TERRAIN {
+            Some(PlasmaTerrain::new(
+                scale_factor_x as usize + 1,
+                scale_factor_z as usize + 1,
+                0,
+                TERRAIN_RELIEF,
+                TERRAIN_RELIEF,
+                TERRAIN_SEED,
+            ))
+        } else {
+            None
+        };
+
         for x in 0..=(scale_factor_x as i32) {
+            let mut row = Vec::with_capacity(scale_factor_z as usize + 1);
             for z in 0..=(scale_factor_z as i32) {
-                let ground_level = ground.level(XZPoint::new(x, z));
-                editor.set_block(groundlayer_block, x, ground_level, z, None, None);
-                editor.set_block(DIRT, x, ground_level - 1, z, None, None);
+                let mut ground_level = ground.level(XZPoint::new(x, z));
+                if let Some(plasma) = &plasma {
+                    ground_level += plasma.height_at(x, z);
+                }
+                let biome = biome_map.biome_at(XZPoint::new(x, z));
+                let surface = if args.winter {
+                    SNOW_BLOCK
+                } else {
+                    biome.surface_block()
+                };
+                editor.set_block(surface, x, ground_level, z, None, None);
+                editor.set_block(biome.filler_block(), x, ground_level - 1, z, None, None);
+
+                row.push(ground_level);
 
                 block_counter += 1;
                 if block_counter % batch_size == 0 {
@@ -214,24 +278,26 @@ pub fn generate_world(
                     last_emitted_progress = gui_progress_grnd;
                 }
             }
+            ground_levels.push(row);
         }
     }
 
-    // Set sign for player orientation
-    /*editor.set_sign(
-        "↑".to_string(),
-        "Generated World".to_string(),
-        "This direction".to_string(),
-        "".to_string(),
-        9,
-        -61,
-        9,
-        6,
-    );*/
+    // Pick an open, flat, dry surface in either mode and lay a landing pad there.
+    // The world spawn in `level.dat` is not changed by this step.
+    if let Some(point) = spawn::select_spawn(&editor, &biome_map, &ground_levels) {
+        spawn::write_spawn(&mut editor, args, &biome_map, &point);
+    }
 
     ground_pb.inc(block_counter % batch_size);
     ground_pb.finish();
 
+    // Optionally emit a top-down preview render of the generated region.
+    if RENDER_MAP {
+        if let Err(e) = map_render::render_map(&biome_map, &ground_levels, args) {
+            eprintln!("{}", e.red());
+        }
+    }
+
     // Save world
     editor.save();
 
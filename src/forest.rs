@@ -0,0 +1,211 @@
+use crate::args::Args;
+use crate::element_processing::natural;
+use crate::geometry::point_in_polygon;
+use crate::ground::Ground;
+use crate::osm_parser::{ProcessedElement, ProcessedNode, ProcessedWay};
+use crate::rng::Rng;
+use crate::world_editor::WorldEditor;
+use std::collections::HashMap;
+
+/// Fixed base seed for the tree sampler, mixed with each way id so placement is
+/// stable across runs without needing a CLI option.
+const FOREST_SEED: u64 = 0xF0_7E57;
+
+/// Graded tree-cover levels, borrowed from embark-assistant's forestation
+/// grades. Each level maps to a trees-per-column scattering rate.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ForestDensity {
+    None,
+    VeryScarce,
+    Scarce,
+    Woodland,
+    HeavilyForested,
+}
+
+impl ForestDensity {
+    /// Expected number of trees per column inside the polygon.
+    fn trees_per_column(self) -> f64 {
+        match self {
+            ForestDensity::None => 0.0,
+            ForestDensity::VeryScarce => 0.002,
+            ForestDensity::Scarce => 0.01,
+            ForestDensity::Woodland => 0.04,
+            ForestDensity::HeavilyForested => 0.10,
+        }
+    }
+
+    /// Derive a density from tags where present, otherwise fall back to a
+    /// sensible default for the feature type.
+    fn from_way(way: &ProcessedWay) -> ForestDensity {
+        // Explicit `density=*`, either a keyword or a numeric OSM value.
+        if let Some(density) = way.tags.get("density") {
+            match density.as_str() {
+                "sparse" | "low" => return ForestDensity::Scarce,
+                "medium" => return ForestDensity::Woodland,
+                "dense" | "high" => return ForestDensity::HeavilyForested,
+                other => {
+                    if let Ok(v) = other.parse::<f64>() {
+                        return Self::from_fraction(v);
+                    }
+                }
+            }
+        }
+        // A single tree line is very sparse cover.
+        if way.tags.get("tree_lined").is_some() {
+            return ForestDensity::VeryScarce;
+        }
+
+        let is_forest = way.tags.get("natural").map(String::as_str) == Some("wood")
+            || way.tags.get("landuse").map(String::as_str) == Some("forest");
+        if is_forest {
+            // Scale the default with the stand's area rather than blanketing
+            // every wood at the maximum rate: `leaf_type` merely marks a managed
+            // stand and nudges it denser, it does not by itself imply a forest.
+            let managed = way.tags.contains_key("leaf_type");
+            return Self::by_area(polygon_area(&way.nodes), managed);
+        }
+        if way.tags.get("leisure").map(String::as_str) == Some("park") {
+            return ForestDensity::Scarce;
+        }
+        ForestDensity::None
+    }
+
+    /// Default density for a wooded stand, scaled by its ground area (in
+    /// blocks²). Small stands stay sparse; only sizeable ones reach the dense
+    /// levels, optionally bumped when the stand is managed.
+    fn by_area(area: f64, managed: bool) -> ForestDensity {
+        match area {
+            a if a < 2_500.0 => {
+                if managed {
+                    ForestDensity::Woodland
+                } else {
+                    ForestDensity::Scarce
+                }
+            }
+            a if a < 40_000.0 => ForestDensity::Woodland,
+            _ => ForestDensity::HeavilyForested,
+        }
+    }
+
+    /// Map a `[0, 1]` canopy fraction onto the graded levels.
+    fn from_fraction(v: f64) -> ForestDensity {
+        match v {
+            x if x <= 0.0 => ForestDensity::None,
+            x if x < 0.1 => ForestDensity::VeryScarce,
+            x if x < 0.3 => ForestDensity::Scarce,
+            x if x < 0.6 => ForestDensity::Woodland,
+            _ => ForestDensity::HeavilyForested,
+        }
+    }
+}
+
+/// Scatter trees across the interior of a wooded polygon at a rate matching its
+/// derived [`ForestDensity`], instead of only rendering individually-tagged
+/// tree nodes. Each sampled point is emitted as a synthetic `natural=tree` node
+/// and handed to [`natural::generate_natural`] so the existing tree renderer is
+/// reused.
+pub fn generate_forest(
+    editor: &mut WorldEditor,
+    way: &ProcessedWay,
+    ground: &Ground,
+    args: &Args,
+) {
+    let density = ForestDensity::from_way(way);
+    let rate = density.trees_per_column();
+    if rate <= 0.0 || way.nodes.len() < 3 {
+        return;
+    }
+
+    let min_x = way.nodes.iter().map(|n| n.x).min().unwrap_or(0);
+    let max_x = way.nodes.iter().map(|n| n.x).max().unwrap_or(0);
+    let min_z = way.nodes.iter().map(|n| n.z).min().unwrap_or(0);
+    let max_z = way.nodes.iter().map(|n| n.z).max().unwrap_or(0);
+
+    // Seed the sampler on the way id so placement is stable across runs.
+    let mut rng = Rng::new(FOREST_SEED ^ way.id);
+
+    for x in min_x..=max_x {
+        for z in min_z..=max_z {
+            if rng.unit() >= rate {
+                continue;
+            }
+            if !point_in_polygon(&way.nodes, x, z) {
+                continue;
+            }
+            let tree = tree_node(x, z);
+            natural::generate_natural(editor, &tree, ground, args);
+        }
+    }
+}
+
+/// Build a synthetic `natural=tree` node at the given column.
+fn tree_node(x: i32, z: i32) -> ProcessedElement {
+    let mut tags = HashMap::new();
+    tags.insert("natural".to_string(), "tree".to_string());
+    ProcessedElement::Node(ProcessedNode {
+        id: 0,
+        x,
+        z,
+        tags,
+    })
+}
+
+/// Ground area of a closed way, in blocks², via the shoelace formula.
+fn polygon_area(nodes: &[ProcessedNode]) -> f64 {
+    if nodes.len() < 3 {
+        return 0.0;
+    }
+    let mut sum: i64 = 0;
+    let mut j = nodes.len() - 1;
+    for i in 0..nodes.len() {
+        sum += (nodes[j].x as i64 + nodes[i].x as i64) * (nodes[j].z as i64 - nodes[i].z as i64);
+        j = i;
+    }
+    (sum.abs() as f64) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(x: i32, z: i32) -> ProcessedNode {
+        ProcessedNode {
+            id: 0,
+            x,
+            z,
+            tags: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn polygon_area_of_square() {
+        let square = [node(0, 0), node(0, 10), node(10, 10), node(10, 0)];
+        assert_eq!(polygon_area(&square), 100.0);
+    }
+
+    #[test]
+    fn polygon_area_of_degenerate_ring_is_zero() {
+        assert_eq!(polygon_area(&[node(0, 0), node(1, 1)]), 0.0);
+    }
+
+    #[test]
+    fn from_fraction_maps_canopy_to_grades() {
+        assert_eq!(ForestDensity::from_fraction(0.0), ForestDensity::None);
+        assert_eq!(ForestDensity::from_fraction(0.05), ForestDensity::VeryScarce);
+        assert_eq!(ForestDensity::from_fraction(0.2), ForestDensity::Scarce);
+        assert_eq!(ForestDensity::from_fraction(0.5), ForestDensity::Woodland);
+        assert_eq!(ForestDensity::from_fraction(0.9), ForestDensity::HeavilyForested);
+    }
+
+    #[test]
+    fn by_area_scales_default_with_stand_size() {
+        assert_eq!(ForestDensity::by_area(100.0, false), ForestDensity::Scarce);
+        assert_eq!(ForestDensity::by_area(100.0, true), ForestDensity::Woodland);
+        assert_eq!(ForestDensity::by_area(3_000.0, false), ForestDensity::Woodland);
+        assert_eq!(
+            ForestDensity::by_area(50_000.0, false),
+            ForestDensity::HeavilyForested
+        );
+    }
+}
+
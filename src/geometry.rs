@@ -0,0 +1,55 @@
+use crate::osm_parser::ProcessedNode;
+
+/// Even-odd ray casting point-in-polygon test over a way's node ring.
+pub fn point_in_polygon(nodes: &[ProcessedNode], x: i32, z: i32) -> bool {
+    if nodes.len() < 3 {
+        return false;
+    }
+    let (px, pz) = (x as f64, z as f64);
+    let mut inside = false;
+    let mut j = nodes.len() - 1;
+    for i in 0..nodes.len() {
+        let (xi, zi) = (nodes[i].x as f64, nodes[i].z as f64);
+        let (xj, zj) = (nodes[j].x as f64, nodes[j].z as f64);
+        if (zi > pz) != (zj > pz) && px < (xj - xi) * (pz - zi) / (zj - zi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn node(x: i32, z: i32) -> ProcessedNode {
+        ProcessedNode {
+            id: 0,
+            x,
+            z,
+            tags: HashMap::new(),
+        }
+    }
+
+    fn square() -> Vec<ProcessedNode> {
+        vec![node(0, 0), node(0, 4), node(4, 4), node(4, 0)]
+    }
+
+    #[test]
+    fn interior_point_is_inside() {
+        assert!(point_in_polygon(&square(), 2, 2));
+    }
+
+    #[test]
+    fn exterior_point_is_outside() {
+        assert!(!point_in_polygon(&square(), 5, 5));
+        assert!(!point_in_polygon(&square(), -1, 2));
+    }
+
+    #[test]
+    fn degenerate_ring_is_never_inside() {
+        assert!(!point_in_polygon(&[node(0, 0), node(1, 1)], 0, 0));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::args::Args;
+use crate::biome::BiomeMap;
+use crate::block_definitions::*;
+use crate::cartesian::XZPoint;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// Render a top-down map image of the generated region.
+///
+/// For every `(x, z)` column the resolved biome's surface block is mapped to a
+/// representative RGB colour and written into a `width*height` PNG. Simple
+/// hillshading is applied from the per-column surface heights in
+/// `ground_levels` so relief is visible. This is a quick preview/thumbnail, in
+/// the spirit of DFHack's `render_map_rect`; it reads the already-computed
+/// generation data rather than scanning the region back out of the editor.
+pub fn render_map(
+    biome_map: &BiomeMap,
+    ground_levels: &[Vec<i32>],
+    args: &Args,
+) -> Result<(), String> {
+    let width = ground_levels.len();
+    if width == 0 {
+        return Ok(());
+    }
+    let depth = ground_levels[0].len();
+
+    let mut pixels: Vec<u8> = Vec::with_capacity(width * depth * 3);
+    for z in 0..depth {
+        for x in 0..width {
+            let biome = biome_map.biome_at(XZPoint::new(x as i32, z as i32));
+            let surface = if args.winter {
+                SNOW_BLOCK
+            } else {
+                biome.surface_block()
+            };
+            let y = ground_levels[x][z];
+            let north = if z > 0 { Some(ground_levels[x][z - 1]) } else { None };
+            let color = shade(block_color(surface), y, north);
+            pixels.extend_from_slice(&color);
+        }
+    }
+
+    let path = format!("{}/map.png", args.path);
+    write_png(&path, width as u32, depth as u32, &pixels)
+        .map_err(|e| format!("Failed to save map render: {e}"))
+}
+
+/// Brighten or darken a colour based on the slope toward the column to the
+/// north, yielding cheap directional hillshading.
+fn shade(color: [u8; 3], y: i32, north: Option<i32>) -> [u8; 3] {
+    let delta = north.map_or(0, |n| y - n).clamp(-4, 4);
+    let factor = 1.0 + delta as f32 * 0.08;
+    color.map(|c| (c as f32 * factor).round().clamp(0.0, 255.0) as u8)
+}
+
+/// Representative RGB colour for a surface block on the preview map.
+fn block_color(block: Block) -> [u8; 3] {
+    if block == GRASS_BLOCK {
+        [106, 153, 85]
+    } else if block == SNOW_BLOCK {
+        [236, 240, 243]
+    } else if block == SAND {
+        [219, 203, 143]
+    } else if block == SANDSTONE {
+        [203, 185, 126]
+    } else if block == MUD {
+        [92, 74, 64]
+    } else if block == WATER {
+        [63, 118, 196]
+    } else if block == DIRT {
+        [134, 96, 67]
+    } else {
+        [150, 150, 150]
+    }
+}
+
+/// Write an RGB buffer as a PNG using only the standard library.
+///
+/// The image data is stored with uncompressed DEFLATE blocks wrapped in a zlib
+/// stream, which keeps the encoder dependency-free at the cost of compression.
+fn write_png(path: &str, width: u32, height: u32, rgb: &[u8]) -> io::Result<()> {
+    let file = File::create(path)?;
+    let mut w = BufWriter::new(file);
+
+    w.write_all(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A])?;
+
+    // IHDR: width, height, 8-bit depth, colour type 2 (truecolour RGB).
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_chunk(&mut w, b"IHDR", &ihdr)?;
+
+    // Raw scanlines, each prefixed with filter-type byte 0.
+    let mut raw = Vec::with_capacity((width as usize * 3 + 1) * height as usize);
+    for row in 0..height as usize {
+        raw.push(0);
+        let start = row * width as usize * 3;
+        raw.extend_from_slice(&rgb[start..start + width as usize * 3]);
+    }
+
+    write_chunk(&mut w, b"IDAT", &zlib_store(&raw))?;
+    write_chunk(&mut w, b"IEND", &[])?;
+    w.flush()
+}
+
+/// Emit a single PNG chunk: length, type, data, CRC.
+fn write_chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    let mut crc = Crc::new();
+    crc.update(kind);
+    crc.update(data);
+    w.write_all(&crc.finish().to_be_bytes())
+}
+
+/// Wrap `data` in a zlib stream using only uncompressed (stored) DEFLATE blocks.
+fn zlib_store(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01]; // zlib header: deflate, default window.
+    let mut offset = 0;
+    while offset < data.len() || data.is_empty() {
+        let len = (data.len() - offset).min(0xFFFF);
+        let final_block = offset + len >= data.len();
+        out.push(if final_block { 1 } else { 0 });
+        out.extend_from_slice(&(len as u16).to_le_bytes());
+        out.extend_from_slice(&(!(len as u16)).to_le_bytes());
+        out.extend_from_slice(&data[offset..offset + len]);
+        offset += len;
+        if final_block {
+            break;
+        }
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Adler-32 checksum over the uncompressed data, as required by zlib.
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % MOD;
+        b = (b + a) % MOD;
+    }
+    (b << 16) | a
+}
+
+/// Standard CRC-32 used for PNG chunk checksums.
+struct Crc {
+    value: u32,
+}
+
+impl Crc {
+    fn new() -> Self {
+        Self { value: 0xFFFF_FFFF }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            let mut c = (self.value ^ byte as u32) & 0xFF;
+            for _ in 0..8 {
+                c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            }
+            self.value = c ^ (self.value >> 8);
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        self.value ^ 0xFFFF_FFFF
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adler32_matches_reference() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"abc"), 0x024D_0127);
+    }
+
+    #[test]
+    fn crc32_matches_reference() {
+        let mut crc = Crc::new();
+        crc.update(b"abc");
+        assert_eq!(crc.finish(), 0x3524_41C2);
+    }
+}
@@ -0,0 +1,36 @@
+/// Small deterministic xorshift RNG, used wherever procedural placement needs
+/// to be reproducible from a seed (terrain, forest scattering).
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: seed.max(1),
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Next value in `[0, 1)`.
+    pub fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A random offset in `[-rough, +rough]`.
+    pub fn offset(&mut self, rough: i32) -> i32 {
+        if rough <= 0 {
+            return 0;
+        }
+        let span = (rough as i64 * 2 + 1) as u64;
+        (self.next() % span) as i32 - rough
+    }
+}
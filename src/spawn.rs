@@ -0,0 +1,134 @@
+use crate::args::Args;
+use crate::biome::{Biome, BiomeMap};
+use crate::block_definitions::*;
+use crate::cartesian::XZPoint;
+use crate::world_editor::WorldEditor;
+
+/// A chosen spawn column: world coordinates of the solid surface block plus the
+/// Y the player stands on (one above the surface).
+pub struct SpawnPoint {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// Scan candidate columns and pick the best open, flat, dry surface to spawn on.
+///
+/// Each column is scored on the local height variance of its neighbourhood in
+/// `ground_levels` (flatter is better); columns in a wet biome (swamp), or
+/// that lack a solid surface, or that have a block occupying either of the two
+/// spaces above the surface (a roof, tree, or wall), are rejected outright. The
+/// baseline editor only reports block presence (`block_at`), so dryness is taken
+/// from the resolved biome rather than the surface block id. This replaces the
+/// old hardcoded grass patch at the origin and is inspired by the respawn-point
+/// records in the carve world model, which pick solid open ground rather than
+/// wherever origin lands.
+pub fn select_spawn(
+    editor: &WorldEditor,
+    biome_map: &BiomeMap,
+    ground_levels: &[Vec<i32>],
+) -> Option<SpawnPoint> {
+    let width = ground_levels.len();
+    if width == 0 {
+        return None;
+    }
+    let depth = ground_levels[0].len();
+
+    let cx = width as i32 / 2;
+    let cz = depth as i32 / 2;
+
+    let mut best: Option<(f64, SpawnPoint)> = None;
+    for x in 1..width as i32 - 1 {
+        for z in 1..depth as i32 - 1 {
+            let y = ground_levels[x as usize][z as usize];
+
+            // Must be dry: reject wet biomes (the baseline editor cannot report
+            // the surface block id, only presence).
+            if biome_map.biome_at(XZPoint::new(x, z)) == Biome::Swamp {
+                continue;
+            }
+            // Must be solid underfoot and walkable: a block at the surface and
+            // the two spaces above it clear.
+            if !editor.block_at(x, y, z) {
+                continue;
+            }
+            if editor.block_at(x, y + 1, z) || editor.block_at(x, y + 2, z) {
+                continue;
+            }
+
+            let variance = local_variance(ground_levels, x, z);
+            // Prefer flat ground, gently biased toward the centre of the region.
+            let dist = (((x - cx).pow(2) + (z - cz).pow(2)) as f64).sqrt();
+            let score = variance + dist * 0.01;
+
+            if best.as_ref().map_or(true, |(b, _)| score < *b) {
+                best = Some((score, SpawnPoint { x, y: y + 1, z }));
+            }
+        }
+    }
+
+    best.map(|(_, p)| p)
+}
+
+/// Lay open, solid ground at the chosen spawn column.
+///
+/// A small flat patch of the column's surface block is laid with the two blocks
+/// above cleared so the landing pad is always walkable regardless of what the
+/// surrounding generation placed. The pad is built from `set_block` only; the
+/// spawn coordinates are not written to `level.dat`.
+pub fn write_spawn(
+    editor: &mut WorldEditor,
+    args: &Args,
+    biome_map: &BiomeMap,
+    point: &SpawnPoint,
+) {
+    let biome = biome_map.biome_at(XZPoint::new(point.x, point.z));
+    let surface = if args.winter {
+        SNOW_BLOCK
+    } else {
+        biome.surface_block()
+    };
+    let floor = point.y - 1;
+    for dx in -1..=1 {
+        for dz in -1..=1 {
+            let (x, z) = (point.x + dx, point.z + dz);
+            editor.set_block(surface, x, floor, z, None, None);
+            editor.set_block(AIR, x, point.y, z, None, None);
+            editor.set_block(AIR, x, point.y + 1, z, None, None);
+        }
+    }
+}
+
+/// Sum of absolute height differences to the four orthogonal neighbours.
+fn local_variance(ground_levels: &[Vec<i32>], x: i32, z: i32) -> f64 {
+    let center = ground_levels[x as usize][z as usize];
+    let neighbours = [
+        ground_levels[(x - 1) as usize][z as usize],
+        ground_levels[(x + 1) as usize][z as usize],
+        ground_levels[x as usize][(z - 1) as usize],
+        ground_levels[x as usize][(z + 1) as usize],
+    ];
+    neighbours.iter().map(|&n| (n - center).abs() as f64).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::local_variance;
+
+    #[test]
+    fn flat_neighbourhood_has_zero_variance() {
+        let levels = vec![vec![64; 3]; 3];
+        assert_eq!(local_variance(&levels, 1, 1), 0.0);
+    }
+
+    #[test]
+    fn variance_sums_absolute_neighbour_differences() {
+        let levels = vec![
+            vec![64, 66, 64],
+            vec![60, 64, 64],
+            vec![64, 67, 64],
+        ];
+        // |60-64| + |64-64| + |66-64| + |67-64| = 4 + 0 + 2 + 3 = 9
+        assert_eq!(local_variance(&levels, 1, 1), 9.0);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::block_definitions::*;
+use crate::data_processing::MIN_Y;
+use crate::world_editor::WorldEditor;
+
+/// Y level below which the stone stratum turns into deepslate.
+const DEEPSLATE_Y: i32 = 0;
+
+/// Definition of a single ore/stone variant placed in the subsurface.
+///
+/// Each block is given an independent, uniform per-block roll against the ore's
+/// `rarity` so that `rarity` reads directly as the fraction of stone replaced;
+/// the smoothed value-noise is reserved for growing compact veins once a seed
+/// block is placed. This mirrors the mineral/ore placement in Minetest, where
+/// each ore carries a rarity and a preferred depth band.
+struct Ore {
+    block: Block,
+    /// Deepslate counterpart used below [`DEEPSLATE_Y`], if any.
+    deep_block: Option<Block>,
+    /// Fraction of eligible stone replaced by a vein seed of this ore.
+    rarity: f64,
+    /// Preferred depth band `[min_y, max_y]` for this ore.
+    min_y: i32,
+    max_y: i32,
+    /// Per-ore seed for the placement roll and vein noise.
+    seed: u64,
+    /// Noise frequency (lattice cells per block); smaller values give larger,
+    /// smoother veins.
+    scale: f64,
+}
+
+/// Ore table, roughly following vanilla distribution. The order matters: the
+/// first ore that wins its roll for a block claims it.
+const ORES: &[Ore] = &[
+    Ore { block: DIORITE,  deep_block: None,                rarity: 0.020, min_y: MIN_Y, max_y: 80,  seed: 0x01, scale: 0.18 },
+    Ore { block: ANDESITE, deep_block: None,                rarity: 0.020, min_y: MIN_Y, max_y: 80,  seed: 0x02, scale: 0.18 },
+    Ore { block: GRANITE,  deep_block: None,                rarity: 0.020, min_y: MIN_Y, max_y: 80,  seed: 0x03, scale: 0.18 },
+    Ore { block: COAL_ORE, deep_block: Some(DEEPSLATE_COAL_ORE),     rarity: 0.012, min_y: 0,   max_y: 128, seed: 0x11, scale: 0.30 },
+    Ore { block: IRON_ORE, deep_block: Some(DEEPSLATE_IRON_ORE),     rarity: 0.009, min_y: -24, max_y: 64,  seed: 0x12, scale: 0.30 },
+    Ore { block: COPPER_ORE, deep_block: Some(DEEPSLATE_COPPER_ORE), rarity: 0.007, min_y: -16, max_y: 64,  seed: 0x13, scale: 0.30 },
+    Ore { block: GOLD_ORE, deep_block: Some(DEEPSLATE_GOLD_ORE),     rarity: 0.002, min_y: MIN_Y, max_y: 32, seed: 0x14, scale: 0.35 },
+    Ore { block: REDSTONE_ORE, deep_block: Some(DEEPSLATE_REDSTONE_ORE), rarity: 0.004, min_y: MIN_Y, max_y: 15, seed: 0x15, scale: 0.35 },
+];
+
+/// Fill the stone column between `bottom` and `top` (inclusive) with the
+/// stratum/ore mix, replacing the previous flat `STONE` fill.
+///
+/// The column is resolved into a local buffer first — base stone, then ore
+/// seeds, then vertical vein growth within that same buffer — and only flushed
+/// to the editor once. Nothing outside the current column is ever written, so
+/// the result is independent of the order columns are generated in; vein shape
+/// comes from the smooth value-noise rather than from mutating neighbours.
+pub fn fill_column(editor: &mut WorldEditor, x: i32, z: i32, bottom: i32, top: i32) {
+    if top < bottom {
+        return;
+    }
+    let height = (top - bottom + 1) as usize;
+
+    // Base stratum for every cell in the column.
+    let mut column: Vec<Block> = (0..height)
+        .map(|i| {
+            let y = bottom + i as i32;
+            if y < DEEPSLATE_Y {
+                DEEPSLATE
+            } else {
+                STONE
+            }
+        })
+        .collect();
+
+    for i in 0..height {
+        let y = bottom + i as i32;
+        // Only unclaimed stone can seed a new vein.
+        if !is_base(column[i]) {
+            continue;
+        }
+        for ore in ORES {
+            if y < ore.min_y || y > ore.max_y {
+                continue;
+            }
+            // Uniform per-block roll: seed a vein with probability `rarity`.
+            if hash(x as i64, y as i64, z as i64, ore.seed) < ore.rarity {
+                column[i] = pick_block(ore, y);
+                grow_vein(&mut column, ore, x, z, bottom, i);
+                break;
+            }
+        }
+    }
+
+    for (i, block) in column.into_iter().enumerate() {
+        editor.set_block(block, x, bottom + i as i32, z, None, None);
+    }
+}
+
+/// Whether a resolved block is still plain stratum (eligible to become ore).
+fn is_base(block: Block) -> bool {
+    block == STONE || block == DEEPSLATE
+}
+
+/// Choose the stone- or deepslate-variant of an ore for the given depth.
+fn pick_block(ore: &Ore, y: i32) -> Block {
+    match ore.deep_block {
+        Some(deep) if y < DEEPSLATE_Y => deep,
+        _ => ore.block,
+    }
+}
+
+/// Grow a compact vein up and down from a seed at index `i`, mutating only the
+/// current column's buffer. A neighbour is claimed where the noise is still
+/// strong, so veins stay compact instead of bleeding across the stratum.
+fn grow_vein(column: &mut [Block], ore: &Ore, x: i32, z: i32, bottom: i32, i: usize) {
+    for di in [-1i32, 1] {
+        let j = i as i32 + di;
+        if j < 0 || j as usize >= column.len() {
+            continue;
+        }
+        let j = j as usize;
+        let ny = bottom + j as i32;
+        if is_base(column[j]) && value_noise(x, ny, z, ore.seed, ore.scale) > 0.6 {
+            column[j] = pick_block(ore, ny);
+        }
+    }
+}
+
+/// Smoothed deterministic 3D value-noise in `[0, 1]`.
+///
+/// Hashes the eight lattice corners surrounding the scaled coordinate and
+/// trilinearly interpolates them, giving clustered values suitable for veins
+/// without pulling in a noise crate.
+fn value_noise(x: i32, y: i32, z: i32, seed: u64, scale: f64) -> f64 {
+    let (fx, fy, fz) = (x as f64 * scale, y as f64 * scale, z as f64 * scale);
+    let (x0, y0, z0) = (fx.floor() as i64, fy.floor() as i64, fz.floor() as i64);
+    let (tx, ty, tz) = (smooth(fx - fx.floor()), smooth(fy - fy.floor()), smooth(fz - fz.floor()));
+
+    let c = |dx: i64, dy: i64, dz: i64| hash(x0 + dx, y0 + dy, z0 + dz, seed);
+    let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+
+    let x00 = lerp(c(0, 0, 0), c(1, 0, 0), tx);
+    let x10 = lerp(c(0, 1, 0), c(1, 1, 0), tx);
+    let x01 = lerp(c(0, 0, 1), c(1, 0, 1), tx);
+    let x11 = lerp(c(0, 1, 1), c(1, 1, 1), tx);
+    let y0i = lerp(x00, x10, ty);
+    let y1i = lerp(x01, x11, ty);
+    lerp(y0i, y1i, tz)
+}
+
+/// Smoothstep easing for the interpolation weights.
+fn smooth(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Hash a lattice corner to a value in `[0, 1]`.
+fn hash(x: i64, y: i64, z: i64, seed: u64) -> f64 {
+    let mut h = seed
+        ^ (x as u64).wrapping_mul(0x9E3779B97F4A7C15)
+        ^ (y as u64).wrapping_mul(0xC2B2AE3D27D4EB4F)
+        ^ (z as u64).wrapping_mul(0x165667B19E3779F9);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xFF51AFD7ED558CCD);
+    h ^= h >> 33;
+    (h >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_in_unit_interval() {
+        for x in -8..8 {
+            for y in -8..8 {
+                let h = hash(x, y, x ^ y, 0x1234);
+                assert!((0.0..1.0).contains(&h), "hash out of range: {h}");
+            }
+        }
+    }
+
+    #[test]
+    fn value_noise_is_in_unit_interval() {
+        for x in 0..32 {
+            for z in 0..32 {
+                let n = value_noise(x, -5, z, 0x42, 0.25);
+                assert!((0.0..=1.0).contains(&n), "noise out of range: {n}");
+            }
+        }
+    }
+}
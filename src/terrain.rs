@@ -0,0 +1,171 @@
+use crate::rng::Rng;
+
+/// Procedural relief synthesized with the diamond-square (plasma fractal)
+/// algorithm, used as a fallback when no real elevation data is fetched.
+///
+/// A square grid of side `2^n + 1` is allocated to cover the region, the four
+/// corners are seeded with a base height, and the diamond/square steps fill in
+/// the interior with a decaying random offset. Heights are clamped to a
+/// `[min, max]` band and averages round up on a remainder to avoid the slow
+/// downward bias that integer truncation would introduce.
+pub struct PlasmaTerrain {
+    /// Side length of the square grid (`2^n + 1`).
+    size: usize,
+    min: i32,
+    max: i32,
+    heights: Vec<i32>,
+}
+
+impl PlasmaTerrain {
+    /// Build a heightfield large enough to cover `width` by `depth` columns.
+    pub fn new(width: usize, depth: usize, min: i32, max: i32, roughness: i32, seed: u64) -> Self {
+        let span = width.max(depth).max(1);
+        let mut n = 0;
+        while (1usize << n) + 1 < span {
+            n += 1;
+        }
+        let size = (1usize << n) + 1;
+
+        let base = (min + max) / 2;
+        let mut terrain = Self {
+            size,
+            min,
+            max,
+            heights: vec![base; size * size],
+        };
+        let mut rng = Rng::new(seed);
+
+        // Seed the four corners.
+        let last = size - 1;
+        for &(x, z) in &[(0, 0), (0, last), (last, 0), (last, last)] {
+            terrain.set(x, z, base);
+        }
+
+        let mut step = last;
+        let mut rough = roughness;
+        while step > 1 {
+            let half = step / 2;
+
+            // Diamond step: centre of each square is the average of its corners.
+            let mut z = half;
+            while z < size {
+                let mut x = half;
+                while x < size {
+                    let avg = terrain.avg4(
+                        terrain.get(x - half, z - half),
+                        terrain.get(x + half, z - half),
+                        terrain.get(x - half, z + half),
+                        terrain.get(x + half, z + half),
+                    );
+                    terrain.set(x, z, avg + rng.offset(rough));
+                    x += step;
+                }
+                z += step;
+            }
+
+            // Square step: each edge midpoint is the average of its neighbours.
+            let mut z = 0;
+            while z < size {
+                let x_start = if (z / half) % 2 == 0 { half } else { 0 };
+                let mut x = x_start;
+                while x < size {
+                    let avg = terrain.avg_neighbours(x, z, half);
+                    terrain.set(x, z, avg + rng.offset(rough));
+                    x += step;
+                }
+                z += half;
+            }
+
+            step /= 2;
+            rough = (rough / 2).max(1);
+        }
+
+        terrain.clamp_all();
+        terrain
+    }
+
+    /// Height synthesized for the given column.
+    pub fn height_at(&self, x: i32, z: i32) -> i32 {
+        let (x, z) = (
+            (x.max(0) as usize).min(self.size - 1),
+            (z.max(0) as usize).min(self.size - 1),
+        );
+        self.get(x, z)
+    }
+
+    fn get(&self, x: usize, z: usize) -> i32 {
+        self.heights[z * self.size + x]
+    }
+
+    fn set(&mut self, x: usize, z: usize, v: i32) {
+        self.heights[z * self.size + x] = v;
+    }
+
+    /// Average of four corners, rounding up on a remainder.
+    fn avg4(&self, a: i32, b: i32, c: i32, d: i32) -> i32 {
+        (a + b + c + d + 3) / 4
+    }
+
+    /// Average of the up-to-four orthogonal neighbours at distance `half`,
+    /// skipping those that fall outside the grid, rounding up on a remainder.
+    fn avg_neighbours(&self, x: usize, z: usize, half: usize) -> i32 {
+        let last = self.size - 1;
+        let mut sum = 0;
+        let mut count = 0;
+        if x >= half {
+            sum += self.get(x - half, z);
+            count += 1;
+        }
+        if x + half <= last {
+            sum += self.get(x + half, z);
+            count += 1;
+        }
+        if z >= half {
+            sum += self.get(x, z - half);
+            count += 1;
+        }
+        if z + half <= last {
+            sum += self.get(x, z + half);
+            count += 1;
+        }
+        (sum + count - 1) / count
+    }
+
+    fn clamp_all(&mut self) {
+        for h in &mut self.heights {
+            *h = (*h).clamp(self.min, self.max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heights_stay_within_band() {
+        let terrain = PlasmaTerrain::new(40, 40, 0, 16, 8, 0xABCD);
+        for z in 0..64 {
+            for x in 0..64 {
+                let h = terrain.height_at(x, z);
+                assert!((0..=16).contains(&h), "height {h} outside band");
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_band_is_constant() {
+        let terrain = PlasmaTerrain::new(20, 20, 7, 7, 4, 0x1);
+        for z in 0..32 {
+            for x in 0..32 {
+                assert_eq!(terrain.height_at(x, z), 7);
+            }
+        }
+    }
+
+    #[test]
+    fn grid_covers_requested_span() {
+        let terrain = PlasmaTerrain::new(50, 30, 0, 10, 4, 0x2);
+        assert!(terrain.size >= 50, "grid too small: {}", terrain.size);
+    }
+}